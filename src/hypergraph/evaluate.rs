@@ -0,0 +1,180 @@
+//! Evaluate an acyclic [`Hypergraph`] by applying a functor from edge labels to functions on
+//! node values.
+//!
+//! This drives a topological (Kahn's-algorithm-style) fold over the bipartite node/edge
+//! incidence encoded by `s` and `t`: edges become ready once every one of their source nodes
+//! has a value, firing writes values to their target nodes, which in turn may make downstream
+//! edges ready.
+
+use crate::array::vec::{VecArray, VecKind};
+use crate::array::*;
+use crate::hypergraph::Hypergraph;
+use crate::semifinite::SemifiniteFunction;
+
+use std::collections::VecDeque;
+
+/// Per edge-label semantics: how to compute the values on an edge's target nodes from the
+/// values on its source nodes.
+pub trait Semantics<V> {
+    /// Compute target node values from source node values, in order.
+    fn apply(&self, inputs: &[V]) -> Vec<V>;
+}
+
+/// [`evaluate`] could not assign every node a value: either the hypergraph's edges form a
+/// cycle (some edge never became ready), or a node was neither a supplied input nor the target
+/// of any edge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CycleError;
+
+/// Interpret an acyclic [`Hypergraph`] as a computation: given a value for each input/boundary
+/// node, fire every edge exactly once (in topological order) to compute a value for every node.
+///
+/// `inputs` must have one entry per node (`Some` for supplied boundary/input nodes, `None`
+/// elsewhere); every other node must be the target of exactly one edge.
+///
+/// A small DAG: a single `Add` operation computing `x + y`.
+///
+/// ```rust
+/// use open_hypergraphs::hypergraph::evaluate::{evaluate, Semantics};
+/// use open_hypergraphs::lax::hypergraph::Hypergraph as LaxHypergraph;
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Add;
+///
+/// impl Semantics<i32> for Add {
+///     fn apply(&self, inputs: &[i32]) -> Vec<i32> {
+///         vec![inputs.iter().sum()]
+///     }
+/// }
+///
+/// let mut h = LaxHypergraph::<(), Add>::empty();
+/// let (_, (sources, targets)) = h.new_operation(Add, vec![(), ()], vec![()]);
+/// let (x, y, z) = (sources[0], sources[1], targets[0]);
+///
+/// let hg = h.to_hypergraph();
+///
+/// let mut inputs = vec![None; 3];
+/// inputs[x.0] = Some(2);
+/// inputs[y.0] = Some(3);
+///
+/// let result = evaluate(&hg, &inputs).unwrap();
+/// assert_eq!(result.0[z.0], 5);
+/// ```
+///
+/// A cycle (`p` feeds `q`, and `q` feeds back into `p`) is rejected instead of looping forever:
+///
+/// ```rust
+/// use open_hypergraphs::hypergraph::evaluate::{evaluate, Semantics};
+/// use open_hypergraphs::lax::hypergraph::{Hyperedge, Hypergraph as LaxHypergraph};
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Identity;
+///
+/// impl Semantics<i32> for Identity {
+///     fn apply(&self, inputs: &[i32]) -> Vec<i32> {
+///         inputs.to_vec()
+///     }
+/// }
+///
+/// let mut h = LaxHypergraph::<(), Identity>::empty();
+/// let p = h.new_node(());
+/// let q = h.new_node(());
+/// h.new_edge(
+///     Identity,
+///     Hyperedge {
+///         sources: vec![p],
+///         targets: vec![q],
+///     },
+/// );
+/// h.new_edge(
+///     Identity,
+///     Hyperedge {
+///         sources: vec![q],
+///         targets: vec![p],
+///     },
+/// );
+///
+/// let hg = h.to_hypergraph();
+/// assert!(evaluate(&hg, &vec![None, None]).is_err());
+/// ```
+pub fn evaluate<O, A, V>(
+    h: &Hypergraph<VecKind, O, A>,
+    inputs: &[Option<V>],
+) -> Result<SemifiniteFunction<VecKind, V>, CycleError>
+where
+    A: Semantics<V>,
+    V: Clone,
+{
+    let num_nodes = h.w.0.len();
+    let num_edges = h.x.0.len();
+    assert_eq!(inputs.len(), num_nodes, "one input per node is required");
+
+    let mut values: Vec<Option<V>> = inputs.to_vec();
+
+    // Flatten each edge's source/target node lists using the offsets implied by `sources`.
+    let s_offsets = h.s.sources.table.cumulative_sum();
+    let t_offsets = h.t.sources.table.cumulative_sum();
+    let edge_sources: Vec<&[usize]> = (0..num_edges)
+        .map(|e| h.s.values.table.get_range(s_offsets.get(e)..s_offsets.get(e + 1)))
+        .collect();
+    let edge_targets: Vec<&[usize]> = (0..num_edges)
+        .map(|e| h.t.values.table.get_range(t_offsets.get(e)..t_offsets.get(e + 1)))
+        .collect();
+
+    // remaining[e] = number of e's source slots without a value yet.
+    let mut remaining: Vec<usize> = edge_sources
+        .iter()
+        .map(|sources| sources.iter().filter(|&&n| values[n].is_none()).count())
+        .collect();
+
+    // node -> edges that have it as a source, so writing a node's value can decrement them.
+    let mut waiting_edges: Vec<Vec<usize>> = vec![vec![]; num_nodes];
+    for (e, sources) in edge_sources.iter().enumerate() {
+        for &n in *sources {
+            waiting_edges[n].push(e);
+        }
+    }
+
+    let mut fired = vec![false; num_edges];
+    let mut queue: VecDeque<usize> = (0..num_edges).filter(|&e| remaining[e] == 0).collect();
+
+    while let Some(e) = queue.pop_front() {
+        if fired[e] {
+            continue;
+        }
+        fired[e] = true;
+
+        let edge_inputs: Vec<V> = edge_sources[e]
+            .iter()
+            .map(|&n| values[n].clone().expect("ready edge has all source values"))
+            .collect();
+        let outputs = h.x.0[e].apply(&edge_inputs);
+
+        for (&n, value) in edge_targets[e].iter().zip(outputs) {
+            values[n] = Some(value);
+            for &e2 in &waiting_edges[n] {
+                if !fired[e2] {
+                    remaining[e2] -= 1;
+                    if remaining[e2] == 0 {
+                        queue.push_back(e2);
+                    }
+                }
+            }
+        }
+    }
+
+    // Either an edge never fired (a cycle), or some node was left without a value despite every
+    // edge firing (it was neither a supplied input nor the target of any edge) -- both are
+    // malformed input from `evaluate`'s point of view, so both are reported the same way rather
+    // than the latter panicking.
+    if fired.iter().any(|&f| !f) || values.iter().any(|v| v.is_none()) {
+        return Err(CycleError);
+    }
+
+    let values: Vec<V> = values
+        .into_iter()
+        .map(|v| v.expect("checked above"))
+        .collect();
+
+    Ok(SemifiniteFunction(VecArray(values)))
+}
@@ -0,0 +1,189 @@
+//! An incremental builder for [`IndexedCoproduct`], backed by a Fenwick tree over per-segment
+//! lengths so offsets can be queried while still building instead of summing from scratch.
+
+use super::arrow::IndexedCoproduct;
+use crate::array::vec::VecArray;
+use crate::array::vec::VecKind;
+use crate::semifinite::SemifiniteFunction;
+
+/// A Fenwick (binary-indexed) tree over segment lengths `0..n`, supporting point updates and
+/// prefix-sum queries in O(log n).
+///
+/// Growing a Fenwick tree one element at a time by simply appending a zero node and leaving
+/// existing nodes alone is unsound: a higher-index node's range can cover several earlier
+/// elements that were updated *before* that node existed, so those updates never propagate into
+/// it. Instead, `lengths` holds the raw per-segment lengths, and `tree` is rebuilt from them
+/// from scratch whenever the tree needs to grow beyond its current power-of-two `capacity`
+/// (capacity doubles like `Vec`'s, and each rebuild re-adds every segment with an O(log n)
+/// point update, so growth costs amortized O(log n) per segment).
+#[derive(Debug, Clone)]
+struct Fenwick {
+    lengths: Vec<usize>,
+    // 1-indexed, of size `capacity + 1`; tree[0] is unused.
+    tree: Vec<usize>,
+    capacity: usize,
+}
+
+impl Fenwick {
+    fn new() -> Self {
+        Fenwick {
+            lengths: Vec::new(),
+            tree: vec![0],
+            capacity: 0,
+        }
+    }
+
+    /// Make room for one more segment, initialized to length zero.
+    fn push_segment(&mut self) {
+        self.lengths.push(0);
+        if self.lengths.len() > self.capacity {
+            self.rebuild();
+        }
+    }
+
+    /// Rebuild `tree` at a larger power-of-two capacity from the current `lengths`.
+    fn rebuild(&mut self) {
+        let mut capacity = self.capacity.max(1);
+        while capacity < self.lengths.len() {
+            capacity *= 2;
+        }
+        self.capacity = capacity;
+        self.tree = vec![0; capacity + 1];
+        for i in 0..self.lengths.len() {
+            let len = self.lengths[i];
+            if len > 0 {
+                self.point_add(i, len);
+            }
+        }
+    }
+
+    fn point_add(&mut self, i: usize, delta: usize) {
+        let mut i = i + 1;
+        while i < self.tree.len() {
+            self.tree[i] += delta;
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    /// Add `delta` to the length of segment `i` (0-indexed).
+    fn add(&mut self, i: usize, delta: usize) {
+        self.lengths[i] += delta;
+        self.point_add(i, delta);
+    }
+
+    /// The sum of the lengths of segments `0..i` (0-indexed, exclusive).
+    fn prefix_sum(&self, i: usize) -> usize {
+        let mut i = i;
+        let mut sum = 0;
+        while i > 0 {
+            sum += self.tree[i];
+            i -= i & i.wrapping_neg();
+        }
+        sum
+    }
+}
+
+/// Builds an [`IndexedCoproduct`] incrementally: segments can be appended/extended in any order
+/// and their flattened offset queried at any time, both in O(log n), with the final
+/// `IndexedCoproduct` materialized in one pass by [`finalize`](IndexedCoproductBuilder::finalize).
+#[derive(Debug, Clone)]
+pub struct IndexedCoproductBuilder<T> {
+    offsets: Fenwick,
+    segments: Vec<Vec<T>>,
+}
+
+impl<T> IndexedCoproductBuilder<T> {
+    /// A builder with no segments.
+    pub fn new() -> Self {
+        IndexedCoproductBuilder {
+            offsets: Fenwick::new(),
+            segments: Vec::new(),
+        }
+    }
+
+    /// Start a new, initially-empty segment, returning its index.
+    pub fn new_segment(&mut self) -> usize {
+        let i = self.segments.len();
+        self.segments.push(Vec::new());
+        self.offsets.push_segment();
+        i
+    }
+
+    /// The number of segments started so far.
+    pub fn num_segments(&self) -> usize {
+        self.segments.len()
+    }
+
+    /// Append a single value to segment `i`.
+    pub fn append_to_segment(&mut self, i: usize, value: T) {
+        self.segments[i].push(value);
+        self.offsets.add(i, 1);
+    }
+
+    /// Append every value of `values` to segment `i`.
+    pub fn extend_segment(&mut self, i: usize, values: impl IntoIterator<Item = T>) {
+        let before = self.segments[i].len();
+        self.segments[i].extend(values);
+        self.offsets.add(i, self.segments[i].len() - before);
+    }
+
+    /// Where segment `i` will start in the flattened `values` array produced by
+    /// [`finalize`](IndexedCoproductBuilder::finalize), without summing every preceding
+    /// segment's length.
+    ///
+    /// This stays correct even when a segment created *before* a later `new_segment()` call is
+    /// appended to again *after* it:
+    ///
+    /// ```rust
+    /// use open_hypergraphs::indexed_coproduct::builder::IndexedCoproductBuilder;
+    ///
+    /// let mut b = IndexedCoproductBuilder::<char>::new();
+    /// let a = b.new_segment();
+    /// b.new_segment();
+    /// b.new_segment();
+    /// b.append_to_segment(a, 'a');
+    ///
+    /// // A new segment is created *after* `a` already has a value...
+    /// let d = b.new_segment();
+    /// // ...and `a` is appended to again *after* that.
+    /// b.append_to_segment(a, 'b');
+    ///
+    /// // The offset spanning the new segment must account for both appends to `a`.
+    /// assert_eq!(b.segment_offset(d), 2);
+    /// ```
+    pub fn segment_offset(&self, i: usize) -> usize {
+        self.offsets.prefix_sum(i)
+    }
+}
+
+impl<T: Clone + PartialEq> IndexedCoproductBuilder<T> {
+    /// Materialize the accumulated segments into an [`IndexedCoproduct`] in one pass.
+    ///
+    /// ```rust
+    /// use open_hypergraphs::indexed_coproduct::builder::IndexedCoproductBuilder;
+    ///
+    /// let mut b = IndexedCoproductBuilder::<usize>::new();
+    /// let a = b.new_segment();
+    /// let c = b.new_segment();
+    /// b.append_to_segment(a, 1);
+    /// b.append_to_segment(a, 2);
+    /// assert_eq!(b.segment_offset(c), 2);
+    /// b.extend_segment(c, vec![3, 4, 5]);
+    ///
+    /// let coproduct = b.finalize();
+    /// assert_eq!(coproduct.values.0.0, vec![1, 2, 3, 4, 5]);
+    /// ```
+    pub fn finalize(self) -> IndexedCoproduct<VecKind, SemifiniteFunction<VecKind, T>> {
+        let sources = SemifiniteFunction(VecArray(
+            self.segments.iter().map(|s| s.len()).collect(),
+        ));
+        let values = SemifiniteFunction(VecArray(self.segments.into_iter().flatten().collect()));
+        IndexedCoproduct::from_semifinite(sources, values).expect("valid IndexedCoproduct")
+    }
+}
+
+impl<T> Default for IndexedCoproductBuilder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
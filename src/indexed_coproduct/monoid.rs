@@ -0,0 +1,118 @@
+//! Monoid-parameterized segmented folds over [`IndexedCoproduct`].
+
+use super::arrow::IndexedCoproduct;
+use crate::array::vec::{VecArray, VecKind};
+use crate::array::*;
+use crate::semifinite::SemifiniteFunction;
+
+/// An associative operation on `T` with an identity element, used to fold the segments of an
+/// [`IndexedCoproduct`].
+///
+/// [`NaturalArray::cumulative_sum`] corresponds to the additive monoid on `usize` (`unit() = 0`,
+/// `combine(a, b) = a + b`): on a coproduct with a single segment, `segmented_scan` yields the
+/// entries of `cumulative_sum` after its leading `0`, and `segmented_reduce` its final total.
+pub trait Monoid<T> {
+    /// The identity element for `combine`.
+    fn unit() -> T;
+
+    /// An associative combination of two elements.
+    fn combine(a: &T, b: &T) -> T;
+}
+
+impl<T: Clone> IndexedCoproduct<VecKind, SemifiniteFunction<VecKind, T>> {
+    /// Fold each segment with `M`, returning one value per segment (`M::unit()` for empty
+    /// segments).
+    ///
+    /// ```rust
+    /// use open_hypergraphs::array::{vec::*, *};
+    /// use open_hypergraphs::indexed_coproduct::{monoid::Monoid, *};
+    /// use open_hypergraphs::semifinite::SemifiniteFunction;
+    ///
+    /// struct Sum;
+    /// impl Monoid<usize> for Sum {
+    ///     fn unit() -> usize {
+    ///         0
+    ///     }
+    ///     fn combine(a: &usize, b: &usize) -> usize {
+    ///         a + b
+    ///     }
+    /// }
+    ///
+    /// let sources = SemifiniteFunction(VecArray(vec![2, 0, 3]));
+    /// let values = SemifiniteFunction(VecArray(vec![1, 2, 3, 4, 5]));
+    /// let c = IndexedCoproduct::from_semifinite(sources, values).unwrap();
+    ///
+    /// let reduced = c.segmented_reduce::<Sum>();
+    /// assert_eq!(reduced, SemifiniteFunction(VecArray(vec![3, 0, 12])));
+    /// ```
+    pub fn segmented_reduce<M: Monoid<T>>(&self) -> SemifiniteFunction<VecKind, T> {
+        let offsets = self.sources.table.cumulative_sum();
+        let n = self.sources.table.len();
+
+        // Accumulate into a single growable buffer instead of reallocating (via `concatenate`)
+        // once per segment, so this stays O(n) overall.
+        let mut result = Vec::with_capacity(n);
+        for i in 0..n {
+            let start = offsets.get(i);
+            let end = offsets.get(i + 1);
+
+            let mut acc = M::unit();
+            for j in start..end {
+                acc = M::combine(&acc, &self.values.0[j]);
+            }
+            result.push(acc);
+        }
+
+        SemifiniteFunction(VecArray(result))
+    }
+
+    /// Compute the per-segment prefix scan of `values` under `M`, keeping `sources` unchanged.
+    ///
+    /// ```rust
+    /// use open_hypergraphs::array::{vec::*, *};
+    /// use open_hypergraphs::indexed_coproduct::{monoid::Monoid, *};
+    /// use open_hypergraphs::semifinite::SemifiniteFunction;
+    ///
+    /// struct Sum;
+    /// impl Monoid<usize> for Sum {
+    ///     fn unit() -> usize {
+    ///         0
+    ///     }
+    ///     fn combine(a: &usize, b: &usize) -> usize {
+    ///         a + b
+    ///     }
+    /// }
+    ///
+    /// let sources = SemifiniteFunction(VecArray(vec![2, 0, 3]));
+    /// let values = SemifiniteFunction(VecArray(vec![1, 2, 3, 4, 5]));
+    /// let c = IndexedCoproduct::from_semifinite(sources, values).unwrap();
+    ///
+    /// let scanned = c.segmented_scan::<Sum>();
+    /// assert_eq!(scanned.values, SemifiniteFunction(VecArray(vec![1, 3, 3, 7, 12])));
+    /// ```
+    pub fn segmented_scan<M: Monoid<T>>(
+        &self,
+    ) -> IndexedCoproduct<VecKind, SemifiniteFunction<VecKind, T>> {
+        let offsets = self.sources.table.cumulative_sum();
+        let n = self.sources.table.len();
+
+        // Accumulate into a single growable buffer instead of reallocating (via `concatenate`)
+        // once per element, so this stays O(n) overall instead of O(n^2).
+        let mut values = Vec::with_capacity(self.values.0.len());
+        for i in 0..n {
+            let start = offsets.get(i);
+            let end = offsets.get(i + 1);
+
+            let mut acc = M::unit();
+            for j in start..end {
+                acc = M::combine(&acc, &self.values.0[j]);
+                values.push(acc.clone());
+            }
+        }
+
+        IndexedCoproduct {
+            sources: self.sources.clone(),
+            values: SemifiniteFunction(VecArray(values)),
+        }
+    }
+}
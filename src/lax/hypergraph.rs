@@ -3,6 +3,8 @@ use crate::{
     finite_function::*,
 };
 
+use super::union_find::DisjointSet;
+
 use core::fmt::Debug;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -39,6 +41,10 @@ pub struct Hypergraph<O, A> {
     // A finite endofunction on the set of nodes, identifying nodes to be quotiented.
     // NOTE: this is a *graph* on the set of nodes.
     pub quotient: (Vec<NodeId>, Vec<NodeId>),
+
+    // A disjoint-set forest over node indices, kept in sync with `quotient` so that `quotient()`
+    // can read off connected components without rescanning the whole quotient graph.
+    uf: DisjointSet,
 }
 
 impl<O, A> Hypergraph<O, A> {
@@ -49,6 +55,7 @@ impl<O, A> Hypergraph<O, A> {
             edges: vec![],
             adjacency: vec![],
             quotient: (vec![], vec![]),
+            uf: DisjointSet::new(),
         }
     }
 
@@ -56,6 +63,7 @@ impl<O, A> Hypergraph<O, A> {
     pub fn new_node(&mut self, w: O) -> NodeId {
         let index = self.nodes.len();
         self.nodes.push(w);
+        self.uf.push();
         NodeId(index)
     }
 
@@ -99,6 +107,10 @@ impl<O, A> Hypergraph<O, A> {
         // add nodes to the quotient graph
         self.quotient.0.push(v);
         self.quotient.1.push(w);
+
+        // merge the two nodes' components immediately, so `quotient()` never has to rescan the
+        // whole quotient graph to recompute connected components.
+        self.uf.union(v.0, w.0);
     }
 
     /// Add a new *source* node labeled `w` to edge `edge_id`.
@@ -122,9 +134,13 @@ impl<O: Clone + PartialEq, A: Clone + PartialEq> Hypergraph<O, A> {
     ///
     /// NOTE: this operation is unchecked; you should verify quotiented nodes have the exact same
     /// type first, or this operation is undefined.
+    ///
+    /// Connected components are read directly off the incrementally-maintained union-find
+    /// forest, so this runs in time proportional to the number of nodes (amortized
+    /// inverse-Ackermann per `find`) rather than rescanning the whole quotient graph.
     pub fn quotient(&mut self) -> FiniteFunction<VecKind> {
         use std::mem::take;
-        let q = self.coequalizer();
+        let q = self.coequalizer_from_union_find();
 
         self.nodes = coequalizer_universal(&q, &VecArray(take(&mut self.nodes)))
             .unwrap()
@@ -139,6 +155,13 @@ impl<O: Clone + PartialEq, A: Clone + PartialEq> Hypergraph<O, A> {
         // clear the quotient map (we just used it)
         self.quotient = (vec![], vec![]); // empty
 
+        // the union-find forest is indexed by the *old* node set; start a fresh one sized to the
+        // nodes that remain after quotienting.
+        self.uf = DisjointSet::new();
+        for _ in 0..self.nodes.len() {
+            self.uf.push();
+        }
+
         q // return the coequalizer used to quotient the hypergraph
     }
 
@@ -146,8 +169,43 @@ impl<O: Clone + PartialEq, A: Clone + PartialEq> Hypergraph<O, A> {
         make_hypergraph(self)
     }
 
-    fn coequalizer(&self) -> FiniteFunction<VecKind> {
-        // Compute the coequalizer (connected components) of the quotient graph
+    /// Build the canonical quotient map from the union-find forest: each class's representative
+    /// is its minimum node index, and classes are relabeled in increasing order of that
+    /// representative, so the map is monotone and satisfies the same universal property as
+    /// [`Hypergraph::coequalizer_slow`].
+    fn coequalizer_from_union_find(&self) -> FiniteFunction<VecKind> {
+        let n = self.nodes.len();
+
+        let mut min_of_root = vec![usize::MAX; n];
+        for i in 0..n {
+            let r = self.uf.find(i);
+            if i < min_of_root[r] {
+                min_of_root[r] = i;
+            }
+        }
+
+        // Each class's minimum node, in increasing order (one per class), without sorting.
+        let mins: Vec<usize> = (0..n)
+            .filter(|&i| min_of_root[self.uf.find(i)] == i)
+            .collect();
+
+        let mut new_index_of_root = vec![0usize; n];
+        for (new_index, &m) in mins.iter().enumerate() {
+            new_index_of_root[self.uf.find(m)] = new_index;
+        }
+
+        let table = (0..n)
+            .map(|i| new_index_of_root[self.uf.find(i)])
+            .collect();
+
+        FiniteFunction::new(VecArray(table), mins.len()).expect("valid quotient map")
+    }
+
+    /// Compute the coequalizer (connected components) of the quotient graph from scratch,
+    /// without consulting the union-find forest. Kept around as a slower reference
+    /// implementation to verify [`Hypergraph::coequalizer_from_union_find`] against (see the
+    /// `coequalizer_from_union_find_matches_slow_path` test below).
+    fn coequalizer_slow(&self) -> FiniteFunction<VecKind> {
         let s: FiniteFunction<VecKind> = FiniteFunction {
             table: VecArray(self.quotient.0.iter().map(|x| x.0).collect()),
             target: self.nodes.len(),
@@ -202,3 +260,24 @@ fn make_hypergraph<O: Clone, A: Clone>(
 
     crate::hypergraph::Hypergraph { s, t, w, x }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn coequalizer_from_union_find_matches_slow_path() {
+        let mut h: Hypergraph<(), ()> = Hypergraph::empty();
+        let nodes: Vec<NodeId> = (0..6).map(|_| h.new_node(())).collect();
+
+        h.unify(nodes[0], nodes[1]);
+        h.unify(nodes[1], nodes[2]);
+        h.unify(nodes[3], nodes[4]);
+
+        let fast = h.coequalizer_from_union_find();
+        let slow = h.coequalizer_slow();
+
+        assert_eq!(fast.table, slow.table);
+        assert_eq!(fast.target, slow.target);
+    }
+}
@@ -0,0 +1,268 @@
+//! Deferred type inference over [`crate::lax::Hypergraph`].
+//!
+//! [`Hypergraph::unify`] and [`Hypergraph::quotient`] defer type-checking: nodes can be
+//! identified before their labels are known to agree. This module lets node labels `O` contain
+//! unification variables, and provides a Robinson-style solver which either replaces each node
+//! label by the term its variable resolves to, or returns a list of the conflicting nodes.
+//! Variables nested inside a constructor's children are not substituted.
+
+use super::hypergraph::{Hypergraph, NodeId};
+use std::collections::HashMap;
+
+/// A unique identifier for a unification variable appearing in a node label.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct VarId(pub usize);
+
+/// Node labels which may be (or contain) unification variables.
+///
+/// Implementors are first-order terms: either a variable, or a constructor applied to some
+/// children. [`zip_children`](Unify::zip_children) plays the role of both: it checks that two
+/// terms have the same head constructor, and if so pairs up their children for recursive
+/// unification. Calling `t.zip_children(t)` (zipping a term against itself) is how the solver
+/// recovers a term's own children, e.g. for the occurs-check.
+pub trait Unify: Clone {
+    /// If this label is a unification variable, its identifier.
+    fn as_var(&self) -> Option<VarId>;
+
+    /// If `self` and `other` have the same head constructor, return their children pairwise so
+    /// each pair can be unified in turn. Returns `None` if the two terms' heads disagree.
+    fn zip_children(&self, other: &Self) -> Option<Vec<(Self, Self)>>;
+}
+
+/// Two node labels could not be unified.
+///
+/// Carries the pair of [`NodeId`]s whose equation (a `quotient` pair, or a source/target pair
+/// from an edge's [`TypingRule`]) produced the conflict.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TypeConflict {
+    pub left: NodeId,
+    pub right: NodeId,
+}
+
+/// An optional per-edge typing rule, used by [`Hypergraph::infer_types`] to additionally
+/// constrain an edge's source/target node labels beyond whatever `quotient` says.
+///
+/// The default implementation imposes no constraints, so edge labels that don't need typing
+/// rules require no extra code.
+pub trait TypingRule<O> {
+    /// Pairs `(source_index, target_index)` whose node labels must unify.
+    fn equations(&self) -> Vec<(usize, usize)> {
+        vec![]
+    }
+}
+
+/// A substitution mapping unification variables to (possibly still-variable) terms, maintained
+/// as a union-find-style binding table.
+struct Substitution<O> {
+    bindings: HashMap<VarId, O>,
+}
+
+impl<O: Unify> Substitution<O> {
+    fn new() -> Self {
+        Substitution {
+            bindings: HashMap::new(),
+        }
+    }
+
+    /// Follow variable bindings until reaching a concrete term or an unbound variable.
+    fn resolve(&self, term: &O) -> O {
+        let mut current = term.clone();
+        while let Some(v) = current.as_var() {
+            match self.bindings.get(&v) {
+                Some(next) => current = next.clone(),
+                None => break,
+            }
+        }
+        current
+    }
+
+    /// Does variable `v` occur (transitively) in `term`? Used to reject infinite types.
+    fn occurs(&self, v: VarId, term: &O) -> bool {
+        let term = self.resolve(term);
+        match term.as_var() {
+            Some(w) => w == v,
+            None => match term.zip_children(&term) {
+                Some(children) => children.iter().any(|(child, _)| self.occurs(v, child)),
+                None => false,
+            },
+        }
+    }
+
+    /// Bind `v` to `term`, failing (without mutating) if this would create an infinite type.
+    /// Records the binding on `trail` so a failed enclosing equation can undo it.
+    fn bind(&mut self, v: VarId, term: O, trail: &mut Vec<VarId>) -> bool {
+        if self.occurs(v, &term) {
+            return false;
+        }
+        self.bindings.insert(v, term);
+        trail.push(v);
+        true
+    }
+
+    /// Unify two terms under the current substitution, extending it in place.
+    ///
+    /// On failure, every binding made while attempting this equation (including ones made by
+    /// child equations that succeeded before a later sibling failed) is rolled back, so a failed
+    /// equation can never leave the substitution in a state that corrupts the solving of other,
+    /// unrelated equations.
+    fn unify(&mut self, a: &O, b: &O) -> bool {
+        let mut trail = Vec::new();
+        if self.unify_with_trail(a, b, &mut trail) {
+            true
+        } else {
+            for v in trail {
+                self.bindings.remove(&v);
+            }
+            false
+        }
+    }
+
+    fn unify_with_trail(&mut self, a: &O, b: &O, trail: &mut Vec<VarId>) -> bool {
+        let a = self.resolve(a);
+        let b = self.resolve(b);
+        match (a.as_var(), b.as_var()) {
+            (Some(v), Some(w)) if v == w => true,
+            (Some(v), _) => self.bind(v, b, trail),
+            (_, Some(w)) => self.bind(w, a, trail),
+            (None, None) => match a.zip_children(&b) {
+                Some(children) => children
+                    .iter()
+                    .all(|(x, y)| self.unify_with_trail(x, y, trail)),
+                None => false,
+            },
+        }
+    }
+}
+
+impl<O: Unify, A: TypingRule<O>> Hypergraph<O, A> {
+    /// Solve for node labels left as unification variables, using the pending `quotient`
+    /// equations (and any per-edge [`TypingRule`]s) as the set of constraints.
+    ///
+    /// On success, every node label in `self.nodes` that is a bound variable is replaced by the
+    /// term its chain of bindings resolves to. Only the top level of a label is resolved:
+    /// variables inside a constructor's children stay as they are, so two unified labels such as
+    /// `Pair(a, Unit)` with `a := Unit` and `Pair(Unit, Unit)` are left unequal. On failure,
+    /// returns every conflicting pair of nodes found; `self` is left unmodified.
+    pub fn infer_types(&mut self) -> Result<(), Vec<TypeConflict>> {
+        let mut subst = Substitution::new();
+        let mut conflicts = Vec::new();
+
+        for (&v, &w) in self.quotient.0.iter().zip(self.quotient.1.iter()) {
+            if !subst.unify(&self.nodes[v.0], &self.nodes[w.0]) {
+                conflicts.push(TypeConflict { left: v, right: w });
+            }
+        }
+
+        for (label, edge) in self.edges.iter().zip(self.adjacency.iter()) {
+            for (i, j) in label.equations() {
+                if let (Some(&v), Some(&w)) = (edge.sources.get(i), edge.targets.get(j)) {
+                    if !subst.unify(&self.nodes[v.0], &self.nodes[w.0]) {
+                        conflicts.push(TypeConflict { left: v, right: w });
+                    }
+                }
+            }
+        }
+
+        if !conflicts.is_empty() {
+            return Err(conflicts);
+        }
+
+        for node in &mut self.nodes {
+            *node = subst.resolve(node);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Debug, PartialEq)]
+    enum Ty {
+        Var(VarId),
+        Unit,
+        Pair(Box<Ty>, Box<Ty>),
+    }
+
+    impl Unify for Ty {
+        fn as_var(&self) -> Option<VarId> {
+            match self {
+                Ty::Var(v) => Some(*v),
+                _ => None,
+            }
+        }
+
+        fn zip_children(&self, other: &Self) -> Option<Vec<(Self, Self)>> {
+            match (self, other) {
+                (Ty::Unit, Ty::Unit) => Some(vec![]),
+                (Ty::Pair(a1, b1), Ty::Pair(a2, b2)) => {
+                    Some(vec![(*a1.clone(), *a2.clone()), (*b1.clone(), *b2.clone())])
+                }
+                _ => None,
+            }
+        }
+    }
+
+    // No edge in these tests imposes extra equations, so the default (empty) impl suffices.
+    impl TypingRule<Ty> for () {}
+
+    #[test]
+    fn infer_types_resolves_unified_variables() {
+        let mut h: Hypergraph<Ty, ()> = Hypergraph::empty();
+        let v = h.new_node(Ty::Var(VarId(0)));
+        let w = h.new_node(Ty::Unit);
+        h.unify(v, w);
+
+        h.infer_types().expect("unification should succeed");
+        assert_eq!(h.nodes[v.0], Ty::Unit);
+    }
+
+    #[test]
+    fn infer_types_reports_conflicting_nodes() {
+        let mut h: Hypergraph<Ty, ()> = Hypergraph::empty();
+        let a = h.new_node(Ty::Unit);
+        let b = h.new_node(Ty::Pair(Box::new(Ty::Unit), Box::new(Ty::Unit)));
+        h.unify(a, b);
+
+        let errs = h.infer_types().unwrap_err();
+        assert_eq!(errs, vec![TypeConflict { left: a, right: b }]);
+    }
+
+    #[test]
+    fn infer_types_rejects_infinite_type() {
+        let mut h: Hypergraph<Ty, ()> = Hypergraph::empty();
+        let v = h.new_node(Ty::Var(VarId(0)));
+        let pair = h.new_node(Ty::Pair(
+            Box::new(Ty::Var(VarId(0))),
+            Box::new(Ty::Unit),
+        ));
+        h.unify(v, pair);
+
+        assert!(h.infer_types().is_err());
+    }
+
+    #[test]
+    fn infer_types_rolls_back_partial_binds_from_a_failed_equation() {
+        let mut h: Hypergraph<Ty, ()> = Hypergraph::empty();
+
+        // This equation fails after successfully unifying its first child (binding Var(0) to
+        // Unit), then hitting a head mismatch on its second child.
+        let a = h.new_node(Ty::Pair(Box::new(Ty::Var(VarId(0))), Box::new(Ty::Unit)));
+        let b = h.new_node(Ty::Pair(
+            Box::new(Ty::Unit),
+            Box::new(Ty::Pair(Box::new(Ty::Unit), Box::new(Ty::Unit))),
+        ));
+        h.unify(a, b);
+
+        // An unrelated equation reusing the same variable. It only succeeds if Var(0)'s
+        // spurious binding from the failed equation above was rolled back.
+        let c = h.new_node(Ty::Var(VarId(0)));
+        let d = h.new_node(Ty::Pair(Box::new(Ty::Unit), Box::new(Ty::Unit)));
+        h.unify(c, d);
+
+        let errs = h.infer_types().unwrap_err();
+        assert_eq!(errs, vec![TypeConflict { left: a, right: b }]);
+    }
+}
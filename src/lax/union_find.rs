@@ -0,0 +1,68 @@
+//! A disjoint-set forest used to maintain [`crate::lax::Hypergraph`]'s quotient incrementally.
+
+use std::cell::Cell;
+
+/// A disjoint-set forest over node indices `0..len()`, supporting `union` and `find` in
+/// amortized inverse-Ackermann time via path compression and union-by-size.
+#[derive(Debug, Clone)]
+pub struct DisjointSet {
+    parent: Vec<Cell<usize>>,
+    size: Vec<usize>,
+}
+
+impl DisjointSet {
+    /// An empty forest with no elements.
+    pub fn new() -> Self {
+        DisjointSet {
+            parent: Vec::new(),
+            size: Vec::new(),
+        }
+    }
+
+    /// Add a new singleton set, returning its index.
+    pub fn push(&mut self) -> usize {
+        let i = self.parent.len();
+        self.parent.push(Cell::new(i));
+        self.size.push(1);
+        i
+    }
+
+    /// The number of elements in the forest.
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Find the representative of the set containing `i`, compressing the path to it.
+    pub fn find(&self, i: usize) -> usize {
+        let parent = self.parent[i].get();
+        if parent == i {
+            return i;
+        }
+        let root = self.find(parent);
+        self.parent[i].set(root);
+        root
+    }
+
+    /// Merge the sets containing `i` and `j`.
+    pub fn union(&mut self, i: usize, j: usize) {
+        let (mut ri, mut rj) = (self.find(i), self.find(j));
+        if ri == rj {
+            return;
+        }
+        if self.size[ri] < self.size[rj] {
+            std::mem::swap(&mut ri, &mut rj);
+        }
+        self.parent[rj].set(ri);
+        self.size[ri] += self.size[rj];
+    }
+}
+
+impl Default for DisjointSet {
+    fn default() -> Self {
+        Self::new()
+    }
+}